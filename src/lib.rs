@@ -47,56 +47,245 @@
 ///                       0003 64 65    de"#)
 /// );
 /// ```
+///
+/// A lone `*` line repeats the previous data line (the convention used by
+/// `od`/`hexdump -v`) until the next line's offset is reached.
+///
+/// ```
+/// assert_eq!(
+///   Some(vec![0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x62]),
+///   hexdump2::import("0000 61 61 61\n*\n0006 62")
+/// );
+/// ```
 pub fn import(data: &str) -> Option<Vec<u8>> {
+  let data = strip_ansi(data);
+  let lines: Vec<&str> = data.split('\n').map(|line| line.trim()).collect();
   let mut buffer = Vec::new();
+  let mut previous_line: Vec<u8> = Vec::new();
+
+  // Offsets recorded in the dump report the true position in the source
+  // `values`, which can start at `skip` rather than `0` (see `export_to`).
+  // `buffer`, on the other hand, is always zero-based, so offsets are
+  // normalized against the first one seen before being compared to it.
+  let base_offset = lines.iter().find_map(|line| parse_offset(line)).unwrap_or(0);
+
+  for (line_index, line) in lines.iter().enumerate() {
+    if *line == "*" {
+      if previous_line.is_empty() {
+        return None;
+      }
+
+      let target_offset = lines[line_index + 1..].iter()
+        .find_map(|line| parse_offset(line))?;
+
+      let target_offset = target_offset.checked_sub(base_offset)?;
+
+      while buffer.len() < target_offset {
+        buffer.extend_from_slice(&previous_line);
+      }
+
+      continue;
+    }
+
+    let line_bytes = import_line(line)?;
+
+    if !line_bytes.is_empty() {
+      previous_line = line_bytes.clone();
+    }
+
+    buffer.extend_from_slice(&line_bytes);
+  }
+
+  Some(buffer)
+}
+
+/// Parses the leading offset field of a hexdump line, if it has one.
+fn parse_offset(line: &str) -> Option<usize> {
+  let word = line.split(' ').next()?.trim_end_matches(':');
+
+  if word.len() > 2 {
+    usize::from_str_radix(word, 16).ok()
+  }
+
+  else {
+    None
+  }
+}
 
-  for line in data.split('\n') {
-    let line = line.trim();
-    let words = line.split(' ');
-    let word_count = line.split(' ').count(); // TODO: improve
-    let mut had_padding = false;
+/// Parses the data bytes out of a single hexdump line.
+fn import_line(line: &str) -> Option<Vec<u8>> {
+  let mut line_buffer = Vec::new();
+  let words = line.split(' ');
+  let word_count = line.split(' ').count(); // TODO: improve
+  let mut had_padding = false;
+  let mut saw_single_byte_cell = false;
 
-    for (index, word) in words.enumerate() {
-      let len = word.len();
+  for (index, word) in words.enumerate() {
+    // offsets produced by xxd end in a colon, e.g. "00000000:"
+    let word = word.trim_end_matches(':');
+    let len = word.len();
 
-      // extra space
-      if len == 0 {
-        had_padding = true;
+    // extra space
+    if len == 0 {
+      had_padding = true;
+      continue;
+    }
+
+    // offset
+    else if index == 0 && len > 2 {
+      continue;
+    }
+
+    else if len == 2 {
+      if index == word_count - 1
+      && had_padding {
         continue;
       }
 
-      // offset
-      else if index == 0 && len > 2 {
+      else if let Ok(value) = u64::from_str_radix(word, 16) {
+        line_buffer.push(value as u8);
+        had_padding = false;
+        saw_single_byte_cell = true;
+      }
+
+      else {
+        return None;
+      }
+    }
+
+    // xxd-style grouped token: several bytes packed into one token with
+    // no internal spacing, e.g. "feff" -> 0xfe, 0xff. A grouped dump uses
+    // the same group width for every data cell on the line, so once we've
+    // seen an ungrouped (2-char) data cell, a later, longer token can only
+    // be the trailing ASCII column, even if it happens to look like hex
+    // (e.g. "cafe") -- so it matches none of these branches and is
+    // silently ignored.
+    else if len > 2 && len % 2 == 0 && !saw_single_byte_cell {
+      if index == word_count - 1
+      && had_padding {
         continue;
       }
 
-      else if len == 2 {
-        if index == word_count - 1
-        && had_padding {
-          continue;
-        }
+      else if let Some(group) = parse_hex_group(word) {
+        line_buffer.extend_from_slice(&group);
+        had_padding = false;
+      }
+    }
+  }
 
-        else if let Ok(value) = u64::from_str_radix(word, 16) {
-          buffer.push(value as u8);
-          had_padding = false;
-        }
+  Some(line_buffer)
+}
+
+/// Splits an even-length run of hex digits into consecutive bytes, e.g.
+/// `"feff"` -> `[0xfe, 0xff]`.
+fn parse_hex_group(word: &str) -> Option<Vec<u8>> {
+  let mut bytes = Vec::with_capacity(word.len() / 2);
+
+  for chunk_start in (0..word.len()).step_by(2) {
+    let byte = u8::from_str_radix(&word[chunk_start..chunk_start + 2], 16).ok()?;
+    bytes.push(byte);
+  }
+
+  Some(bytes)
+}
+
+/// Strips ANSI escape sequences (as produced by `with_color`) so a colored
+/// dump still round-trips through `import`.
+fn strip_ansi(data: &str) -> String {
+  let mut res = String::with_capacity(data.len());
+  let mut chars = data.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    if c == '\u{1b}' && chars.peek() == Some(&'[') {
+      chars.next();
 
-        else {
-          return None;
+      while let Some(&next) = chars.peek() {
+        chars.next();
+
+        if next.is_alphabetic() {
+          break;
         }
       }
+
+      continue;
     }
+
+    res.push(c);
   }
 
-  Some(buffer)
+  res
 }
 
 pub struct ExportOptions {
   pub per_line: usize,
   pub with_offsets: bool,
   pub with_ascii: bool,
+  pub format: ByteFormat,
+  pub with_color: bool,
+  /// Number of consecutive bytes packed into a single column with no
+  /// internal spacing (1, 2, 4, or 8), xxd-style.
+  pub group_size: usize,
+  /// Collapse consecutive identical full lines into a single line followed
+  /// by a `*`, the convention used by `od`/`hexdump -v`.
+  pub squeeze: bool,
+  /// Number of bytes to skip from the start of `values` before dumping.
+  pub skip: usize,
+  /// Number of bytes to dump after `skip`. `None` dumps through the end.
+  pub length: Option<usize>,
+}
+
+impl Default for ExportOptions {
+  fn default() -> Self {
+    ExportOptions {
+      per_line: 16,
+      with_offsets: false,
+      with_ascii: false,
+      format: ByteFormat::default(),
+      with_color: false,
+      group_size: 1,
+      squeeze: false,
+      skip: 0,
+      length: None,
+    }
+  }
+}
+
+/// Selects how each byte is rendered in a hexdump cell.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum ByteFormat {
+  /// `61` (the default)
+  #[default]
+  UpperHex,
+  /// `61`
+  LowerHex,
+  /// `141`
+  Octal,
+  /// `01100001`
+  Binary,
+  /// ` 97`
+  Decimal,
 }
 
+impl ByteFormat {
+  /// Width in characters of a single formatted cell.
+  fn cell_width(&self) -> usize {
+    match *self {
+      ByteFormat::UpperHex | ByteFormat::LowerHex => 2,
+      ByteFormat::Octal | ByteFormat::Decimal => 3,
+      ByteFormat::Binary => 8,
+    }
+  }
+
+  fn format(&self, value: u8) -> String {
+    match *self {
+      ByteFormat::UpperHex => format!("{:02X}", value),
+      ByteFormat::LowerHex => format!("{:02x}", value),
+      ByteFormat::Octal => format!("{:03o}", value),
+      ByteFormat::Binary => format!("{:08b}", value),
+      ByteFormat::Decimal => format!("{:3}", value),
+    }
+  }
+}
 
 /// Exports a slice of bytes into a hexdump string.
 pub fn export(
@@ -114,37 +303,166 @@ pub enum ExportError {
   BadOptions,
 }
 
+/// Target language for [`export_array`].
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum ArrayLanguage {
+  /// `unsigned char name[N] = { 0x61, 0x62 };`
+  C,
+  /// `const NAME: [u8; N] = [0x61, 0x62];`
+  Rust,
+  /// `0x61, 0x62`, with no surrounding declaration.
+  #[default]
+  Plain,
+}
+
+pub struct ArrayOptions {
+  pub per_line: usize,
+  pub indent: usize,
+  pub language: ArrayLanguage,
+  pub name: String,
+}
+
+impl Default for ArrayOptions {
+  fn default() -> Self {
+    ArrayOptions {
+      per_line: 12,
+      indent: 2,
+      language: ArrayLanguage::Plain,
+      name: String::from("data"),
+    }
+  }
+}
+
+/// Exports a slice of bytes as a source-code array literal, ready to paste
+/// into a C or Rust file (or as a bare comma-separated list).
+pub fn export_array(
+  values: &[u8],
+  options: ArrayOptions
+) -> Result<String, ExportError> {
+  let mut body = String::new();
+  write_array_body(&mut body, values, &options);
+
+  let res = match options.language {
+    ArrayLanguage::C =>
+      format!("unsigned char {}[{}] = {{\n{}\n}};",
+              options.name, values.len(), body),
+
+    ArrayLanguage::Rust =>
+      format!("const {}: [u8; {}] = [\n{}\n];",
+              options.name.to_uppercase(), values.len(), body),
+
+    ArrayLanguage::Plain => body,
+  };
+
+  Ok(res)
+}
+
+fn write_array_body<T: ::std::fmt::Write>(
+  target: &mut T,
+  values: &[u8],
+  options: &ArrayOptions
+) {
+  let indent = " ".repeat(options.indent);
+  let total_value_count = values.len();
+  let mut line_value_count = 0;
+
+  for (index, value) in values.iter().enumerate() {
+    if line_value_count == 0 {
+      target.write_str(&indent).unwrap();
+    }
+
+    target.write_str(&format!("0x{:02x}", value)).unwrap();
+    line_value_count += 1;
+
+    let is_last_value = index == total_value_count - 1;
+
+    if !is_last_value {
+      target.write_char(',').unwrap();
+    }
+
+    let is_last_value_for_this_line = line_value_count == options.per_line;
+
+    if is_last_value_for_this_line || is_last_value {
+      line_value_count = 0;
+
+      if !is_last_value {
+        target.write_char('\n').unwrap();
+      }
+    }
+
+    else {
+      target.write_char(' ').unwrap();
+    }
+  }
+}
+
 /// Exports a slice of bytes into a writable.
+///
+/// `options.skip` and `options.length` select a window of `values` to dump;
+/// offsets in the output still report the true position within `values`
+/// rather than the position within the emitted window.
+///
+/// Returns [`ExportError::BadOptions`] if `options.per_line` is `0`, since
+/// there would be no way to lay out a single line of output, if
+/// `options.group_size` isn't one of `1`, `2`, `4`, or `8`, if
+/// `options.group_size` is greater than `1` while `options.with_offsets`
+/// is `false` -- without offsets, `import` can't tell a grouped data cell
+/// from the leading offset field, and would silently drop the first group
+/// -- if `options.group_size` is greater than `1` while `options.with_ascii`
+/// is `true` -- the trailing ASCII column is then the same shape as a
+/// grouped data cell, and `import` can't tell them apart -- or if
+/// `options.squeeze` is `true` while `options.with_offsets` is `false` --
+/// `import`'s `*` expansion needs a following line's offset to know how
+/// many repeats to synthesize.
 pub fn export_to<T: ::std::fmt::Write>(
   target: &mut T,
   values: &[u8],
   options: ExportOptions
 ) -> Result<(), ExportError> {
+  if options.per_line == 0
+  || !is_valid_group_size(options.group_size)
+  || (options.group_size > 1 && !options.with_offsets)
+  || (options.group_size > 1 && options.with_ascii)
+  || (options.squeeze && !options.with_offsets) {
+    return Err(ExportError::BadOptions);
+  }
+
+  let values = windowed(values, options.skip, options.length);
   let total_value_count = values.len();
+  let offset_width_bound = options.skip + total_value_count;
   let mut line_value_count = 0;
+  let mut line_start = 0;
   let mut ascii = String::new();
+  let mut line = String::new();
+  let mut previous_line_values: Vec<u8> = Vec::new();
+  let mut is_squeezing = false;
+  let mut is_first_output_line = true;
 
   for (index, value) in values.iter().enumerate() {
     if options.with_offsets
     && index % options.per_line == 0 {
-      write_offset(target, index, total_value_count).unwrap();
+      write_offset(&mut line, options.skip + index, offset_width_bound).unwrap();
     }
 
-    target.write_str(&format!("{:02X}", *value)).unwrap();
+    write_cell(&mut line, options.format.format(*value), *value,
+               options.with_color).unwrap();
     line_value_count += 1;
 
     if options.with_ascii {
-      push_ascii(&mut ascii, *value);
+      push_ascii(&mut ascii, *value, options.with_color);
     }
 
     let is_last_value = index == total_value_count - 1;
 
     if is_last_value {
       if options.with_ascii {
-        write_ascii(target, &ascii, line_value_count,
-                    options.per_line).unwrap();
+        write_ascii(&mut line, &ascii, line_value_count, options.per_line,
+                    options.format.cell_width(), options.group_size).unwrap();
       }
 
+      // always emit the final line in full, even mid-squeeze-run, so the
+      // dump's real length stays recoverable on import.
+      flush_line(target, &line, &mut is_first_output_line).unwrap();
       continue;
     }
 
@@ -152,24 +470,304 @@ pub fn export_to<T: ::std::fmt::Write>(
 
     if is_last_value_for_this_line {
       if options.with_ascii {
-        write_ascii(target, &ascii, line_value_count,
-                    options.per_line).unwrap();
+        write_ascii(&mut line, &ascii, line_value_count, options.per_line,
+                    options.format.cell_width(), options.group_size).unwrap();
         ascii.clear();
       }
 
-      target.write_char('\n').unwrap();
+      let current_line_values = &values[line_start..index + 1];
+      let repeats_previous_line =
+        options.squeeze && current_line_values == previous_line_values.as_slice();
+
+      if repeats_previous_line {
+        if !is_squeezing {
+          flush_line(target, "*", &mut is_first_output_line).unwrap();
+          is_squeezing = true;
+        }
+      }
+
+      else {
+        flush_line(target, &line, &mut is_first_output_line).unwrap();
+        is_squeezing = false;
+      }
+
+      previous_line_values = current_line_values.to_vec();
+      line.clear();
+      line_start = index + 1;
       line_value_count = 0;
       continue;
     }
 
-    if line_value_count < options.per_line {
-      target.write_char(' ').unwrap();
+    let ends_a_group = line_value_count % options.group_size == 0;
+
+    if ends_a_group && line_value_count < options.per_line {
+      line.push(' ');
+    }
+  }
+
+  Ok(())
+}
+
+/// Whether `group_size` is one of the widths `export_to`/`export_stream`
+/// accept, xxd-style: 1, 2, 4, or 8 bytes per column.
+fn is_valid_group_size(group_size: usize) -> bool {
+  matches!(group_size, 1 | 2 | 4 | 8)
+}
+
+/// Slices `values` down to the `skip`/`length` window, clamping both to
+/// `values`' bounds so an out-of-range window yields an empty slice rather
+/// than panicking.
+fn windowed(values: &[u8], skip: usize, length: Option<usize>) -> &[u8] {
+  let start = skip.min(values.len());
+  let end = match length {
+    Some(length) => start.saturating_add(length).min(values.len()),
+    None => values.len(),
+  };
+
+  &values[start..end]
+}
+
+fn flush_line<T: ::std::fmt::Write>(
+  target: &mut T,
+  line: &str,
+  is_first_output_line: &mut bool
+) -> Result<(), ::std::fmt::Error> {
+  if !*is_first_output_line {
+    target.write_char('\n')?;
+  }
+
+  *is_first_output_line = false;
+  target.write_str(line)
+}
+
+/// Exports a reader's bytes into a writer, formatting and flushing one line
+/// at a time so the full input never has to fit in memory.
+///
+/// Because the total length isn't known up front, offsets are always
+/// printed 8 hex digits wide (growing to 16 once the offset itself
+/// exceeds that range), unlike `export_to`'s total-length-based width.
+///
+/// `options.skip` and `options.length` select a window of the reader to
+/// dump, same as `export_to`: `skip` bytes are read and discarded before
+/// the first emitted line, and reading stops once `length` bytes (if set)
+/// have been emitted, without requiring the reader to hit EOF.
+///
+/// `options.squeeze` is honored the same way as in `export_to`: runs of
+/// consecutive identical lines collapse to a single `*`. Memory stays
+/// bounded since only the single most recently read line is held back
+/// (to find out whether it repeats, and whether it's the last line).
+///
+/// Returns an error if `options.per_line` is `0`, since there would be no
+/// way to lay out a single line of output, if `options.group_size` isn't
+/// one of `1`, `2`, `4`, or `8`, if `options.group_size` is greater than
+/// `1` while `options.with_offsets` is `false` or `options.with_ascii` is
+/// `true`, or if `options.squeeze` is `true` while `options.with_offsets`
+/// is `false` (see `export_to`).
+pub fn export_stream<R: ::std::io::Read, W: ::std::io::Write>(
+  reader: &mut R,
+  writer: &mut W,
+  options: ExportOptions
+) -> ::std::io::Result<()> {
+  if options.per_line == 0
+  || !is_valid_group_size(options.group_size)
+  || (options.group_size > 1 && !options.with_offsets)
+  || (options.group_size > 1 && options.with_ascii)
+  || (options.squeeze && !options.with_offsets) {
+    return Err(::std::io::Error::new(
+      ::std::io::ErrorKind::InvalidInput,
+      "ExportOptions::per_line must be non-zero, group_size must be 1, 2, \
+       4, or 8, group_size > 1 requires with_offsets, group_size > 1 is \
+       incompatible with with_ascii, and squeeze requires with_offsets"
+    ));
+  }
+
+  if options.skip > 0 && !discard_bytes(reader, options.skip)? {
+    return Ok(());
+  }
+
+  let mut chunk = vec![0u8; options.per_line.max(1)];
+  let mut offset = options.skip;
+  let mut remaining_length = options.length;
+  let mut is_first_line = true;
+
+  // The most recently read line is held back by one iteration so we can
+  // tell, once the next read comes back, whether it repeats (and should
+  // be squeezed) or is in fact the last line (which is always emitted in
+  // full, even mid-squeeze-run, same as `export_to`).
+  let mut pending: Option<(usize, Vec<u8>)> = None;
+  let mut previous_values: Vec<u8> = Vec::new();
+  let mut is_squeezing = false;
+
+  loop {
+    let want = match remaining_length {
+      Some(0) => break,
+      Some(remaining) => remaining.min(chunk.len()),
+      None => chunk.len(),
+    };
+
+    let read = fill_chunk(reader, &mut chunk[..want])?;
+
+    if read == 0 {
+      break;
+    }
+
+    if let Some((prev_offset, prev_values)) = pending.take() {
+      let repeats_previous = options.squeeze && prev_values == previous_values;
+
+      if repeats_previous {
+        if !is_squeezing {
+          flush_stream_line(writer, "*", &mut is_first_line)?;
+          is_squeezing = true;
+        }
+      }
+
+      else {
+        write_and_flush_stream_line(writer, &prev_values, prev_offset,
+                                     &options, &mut is_first_line)?;
+        is_squeezing = false;
+      }
+
+      previous_values = prev_values;
     }
+
+    pending = Some((offset, chunk[..read].to_vec()));
+    offset += read;
+
+    if let Some(remaining) = remaining_length.as_mut() {
+      *remaining -= read;
+    }
+
+    if read < want {
+      break;
+    }
+  }
+
+  if let Some((prev_offset, prev_values)) = pending {
+    write_and_flush_stream_line(writer, &prev_values, prev_offset,
+                                 &options, &mut is_first_line)?;
   }
 
   Ok(())
 }
 
+/// Reads and discards `count` bytes from `reader`, the streaming
+/// equivalent of `windowed`'s `skip`. Returns `false` if the reader runs
+/// out before `count` bytes are consumed, meaning nothing is left to dump.
+fn discard_bytes<R: ::std::io::Read>(
+  reader: &mut R,
+  count: usize
+) -> ::std::io::Result<bool> {
+  let mut remaining = count;
+  let mut buf = [0u8; 4096];
+
+  while remaining > 0 {
+    let want = remaining.min(buf.len());
+    let read = fill_chunk(reader, &mut buf[..want])?;
+
+    if read < want {
+      return Ok(false);
+    }
+
+    remaining -= read;
+  }
+
+  Ok(true)
+}
+
+/// Reads until `buf` is full or the reader is exhausted, tolerating readers
+/// that return short reads before EOF.
+fn fill_chunk<R: ::std::io::Read>(
+  reader: &mut R,
+  buf: &mut [u8]
+) -> ::std::io::Result<usize> {
+  let mut filled = 0;
+
+  while filled < buf.len() {
+    match reader.read(&mut buf[filled..])? {
+      0 => break,
+      n => filled += n,
+    }
+  }
+
+  Ok(filled)
+}
+
+/// Writes `\n` before `line` unless it's the first line written, the
+/// streaming equivalent of `flush_line`.
+fn flush_stream_line<W: ::std::io::Write>(
+  writer: &mut W,
+  line: &str,
+  is_first_line: &mut bool
+) -> ::std::io::Result<()> {
+  if !*is_first_line {
+    writer.write_all(b"\n")?;
+  }
+
+  *is_first_line = false;
+  writer.write_all(line.as_bytes())
+}
+
+/// Formats `values` as a single hexdump line at `offset` and flushes it to
+/// `writer`.
+fn write_and_flush_stream_line<W: ::std::io::Write>(
+  writer: &mut W,
+  values: &[u8],
+  offset: usize,
+  options: &ExportOptions,
+  is_first_line: &mut bool
+) -> ::std::io::Result<()> {
+  let mut line = String::new();
+  write_stream_line(&mut line, values, offset, options);
+  flush_stream_line(writer, &line, is_first_line)
+}
+
+fn write_stream_line<T: ::std::fmt::Write>(
+  target: &mut T,
+  values: &[u8],
+  offset: usize,
+  options: &ExportOptions,
+) {
+  let mut ascii = String::new();
+
+  if options.with_offsets {
+    write_stream_offset(target, offset).unwrap();
+  }
+
+  for (index, value) in values.iter().enumerate() {
+    write_cell(target, options.format.format(*value), *value,
+               options.with_color).unwrap();
+
+    if options.with_ascii {
+      push_ascii(&mut ascii, *value, options.with_color);
+    }
+
+    let line_value_count = index + 1;
+    let ends_a_group = line_value_count % options.group_size == 0;
+
+    if ends_a_group && line_value_count < values.len() {
+      target.write_char(' ').unwrap();
+    }
+  }
+
+  if options.with_ascii {
+    write_ascii(target, &ascii, values.len(), options.per_line,
+                options.format.cell_width(), options.group_size).unwrap();
+  }
+}
+
+fn write_stream_offset<T: ::std::fmt::Write>(
+  target: &mut T,
+  offset: usize
+) -> Result<(), ::std::fmt::Error> {
+  if offset <= 0xffffffff {
+    target.write_str(&format!("{:08X} ", offset))
+  }
+
+  else {
+    target.write_str(&format!("{:16X} ", offset))
+  }
+}
+
 fn write_offset<T: ::std::fmt::Write>(
   target: &mut T,
   index: usize,
@@ -188,26 +786,81 @@ fn write_offset<T: ::std::fmt::Write>(
   }
 }
 
-fn push_ascii(ascii: &mut String, value: u8) {
-  if value >= 0x20 && value <= 0x7e {
-    ascii.push(value as char);
+fn push_ascii(ascii: &mut String, value: u8, with_color: bool) {
+  let ch = if value >= 0x20 && value <= 0x7e {
+    value as char
+  }
+
+  else {
+    '.'
+  };
+
+  if with_color {
+    ascii.push_str(ansi_color(value));
+    ascii.push(ch);
+    ascii.push_str(ANSI_RESET);
+  }
+
+  else {
+    ascii.push(ch);
+  }
+}
+
+const ANSI_RESET: &str = "\u{1b}[0m";
+
+/// ANSI color for a byte, grouped by class: NUL, printable ASCII,
+/// whitespace/control, and high bytes.
+fn ansi_color(value: u8) -> &'static str {
+  match value {
+    0x00 => "\u{1b}[90m",
+    0x20..=0x7e => "\u{1b}[32m",
+    0x80..=0xff => "\u{1b}[31m",
+    _ => "\u{1b}[33m",
+  }
+}
+
+fn write_cell<T: ::std::fmt::Write>(
+  target: &mut T,
+  cell: String,
+  value: u8,
+  with_color: bool
+) -> Result<(), ::std::fmt::Error> {
+  if with_color {
+    target.write_str(ansi_color(value))?;
+    target.write_str(&cell)?;
+    target.write_str(ANSI_RESET)
   }
 
   else {
-    ascii.push('.');
+    target.write_str(&cell)
   }
 }
 
+/// Width in characters of `byte_count` formatted cells, accounting for the
+/// single separating space between (but not within) `group_size`-byte groups.
+fn hex_area_width(byte_count: usize, group_size: usize, cell_width: usize) -> usize {
+  if byte_count == 0 {
+    return 0;
+  }
+
+  let group_count = byte_count.div_ceil(group_size);
+
+  byte_count * cell_width + (group_count - 1)
+}
+
 fn write_ascii<T: ::std::fmt::Write>(
   target: &mut T,
   ascii: &String,
   count: usize,
   per_line: usize,
+  cell_width: usize,
+  group_size: usize,
 ) -> Result<(), ::std::fmt::Error> {
-  let missing_value_count = per_line - count;
+  let full_width = hex_area_width(per_line, group_size, cell_width);
+  let actual_width = hex_area_width(count, group_size, cell_width);
 
-  for _ in 0..missing_value_count {
-    target.write_str("   ")?;
+  for _ in 0..(full_width - actual_width) {
+    target.write_char(' ')?;
   }
   target.write_char(' ')?;
   target.write_str(&ascii)
@@ -256,6 +909,23 @@ mod tests {
     );
   }
 
+  #[test]
+  fn import_with_offset_colon() {
+    assert_eq!(
+      Some(vec![0x00, 0x01, 0xfe, 0xff]),
+      import("00000000: 00 01 fe ff")
+    );
+  }
+
+  #[test]
+  fn import_with_grouped_bytes() {
+    // "0000" is the offset; the remaining tokens are 2-byte groups.
+    assert_eq!(
+      Some(vec![0xfe, 0xff, 0x61, 0x62, 0x63, 0x64]),
+      import("0000 feff 6162 6364")
+    );
+  }
+
   #[test]
   fn exports_bytes() {
     let mut actual = String::new();
@@ -263,6 +933,7 @@ mod tests {
       with_ascii: false,
       with_offsets: false,
       per_line: 16,
+      ..Default::default()
     }));
     assert_eq!("00 01 02 03", &actual);
   }
@@ -274,10 +945,57 @@ mod tests {
       with_ascii: false,
       with_offsets: false,
       per_line: 2,
+      ..Default::default()
     }));
     assert_eq!("00 01\n02 03", &actual);
   }
 
+  #[test]
+  fn export_to_rejects_zero_per_line() {
+    let mut actual = String::new();
+    assert_eq!(Err(ExportError::BadOptions), export_to(&mut actual, &[0, 1], ExportOptions {
+      per_line: 0,
+      ..Default::default()
+    }));
+  }
+
+  #[test]
+  fn export_stream_rejects_zero_per_line() {
+    let mut reader = ::std::io::Cursor::new(vec![0x00, 0x01]);
+    let mut writer = Vec::new();
+
+    assert_eq!(
+      ::std::io::ErrorKind::InvalidInput,
+      export_stream(&mut reader, &mut writer, ExportOptions {
+        per_line: 0,
+        ..Default::default()
+      }).unwrap_err().kind()
+    );
+  }
+
+  #[test]
+  fn export_to_rejects_invalid_group_size() {
+    let mut actual = String::new();
+    assert_eq!(Err(ExportError::BadOptions), export_to(&mut actual, &[0, 1], ExportOptions {
+      group_size: 3,
+      ..Default::default()
+    }));
+  }
+
+  #[test]
+  fn export_stream_rejects_invalid_group_size() {
+    let mut reader = ::std::io::Cursor::new(vec![0x00, 0x01]);
+    let mut writer = Vec::new();
+
+    assert_eq!(
+      ::std::io::ErrorKind::InvalidInput,
+      export_stream(&mut reader, &mut writer, ExportOptions {
+        group_size: 3,
+        ..Default::default()
+      }).unwrap_err().kind()
+    );
+  }
+
   #[test]
   fn exports_offsets_and_bytes() {
     let mut actual = String::new();
@@ -285,6 +1003,7 @@ mod tests {
       with_ascii: false,
       with_offsets: true,
       per_line: 2,
+      ..Default::default()
     }));
     assert_eq!("0000 00 01\n0002 02 03", &actual);
   }
@@ -299,6 +1018,7 @@ mod tests {
       with_ascii: true,
       with_offsets: true,
       per_line: 4,
+      ..Default::default()
     }));
     assert_eq!("0000 61 62 63 64 abcd\n0004 65 00 19 7F e...", &actual);
   }
@@ -313,6 +1033,7 @@ mod tests {
       with_ascii: true,
       with_offsets: true,
       per_line: 4,
+      ..Default::default()
     }));
     assert_eq!("0000 61 62 63 64 abcd\n0004 65 00       e.", &actual);
   }
@@ -325,7 +1046,439 @@ mod tests {
         with_ascii: false,
         with_offsets: false,
         per_line: 4,
+        ..Default::default()
+      })
+    );
+  }
+
+  #[test]
+  fn exports_lower_hex() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[0, 0x1a, 0xff], ExportOptions {
+      with_ascii: false,
+      with_offsets: false,
+      per_line: 16,
+      format: ByteFormat::LowerHex,
+      ..Default::default()
+    }));
+    assert_eq!("00 1a ff", &actual);
+  }
+
+  #[test]
+  fn exports_octal() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[0, 1, 0xff], ExportOptions {
+      with_ascii: false,
+      with_offsets: false,
+      per_line: 16,
+      format: ByteFormat::Octal,
+      ..Default::default()
+    }));
+    assert_eq!("000 001 377", &actual);
+  }
+
+  #[test]
+  fn exports_binary() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[0, 0xff], ExportOptions {
+      with_ascii: false,
+      with_offsets: false,
+      per_line: 16,
+      format: ByteFormat::Binary,
+      ..Default::default()
+    }));
+    assert_eq!("00000000 11111111", &actual);
+  }
+
+  #[test]
+  fn exports_decimal() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[0, 97, 255], ExportOptions {
+      with_ascii: false,
+      with_offsets: false,
+      per_line: 16,
+      format: ByteFormat::Decimal,
+      ..Default::default()
+    }));
+    assert_eq!("  0  97 255", &actual);
+  }
+
+  #[test]
+  fn exports_binary_with_ascii_and_partial_last_line() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[0x61, 0x62, 0x63], ExportOptions {
+      with_ascii: true,
+      with_offsets: false,
+      per_line: 4,
+      format: ByteFormat::Binary,
+      ..Default::default()
+    }));
+    assert_eq!("01100001 01100010 01100011          abc", &actual);
+  }
+
+  #[test]
+  fn exports_array_plain() {
+    assert_eq!(
+      Ok(String::from("  0x61, 0x62, 0x63")),
+      export_array(&[0x61, 0x62, 0x63], ArrayOptions {
+        per_line: 16,
+        ..Default::default()
       })
     );
   }
+
+  #[test]
+  fn exports_array_c() {
+    assert_eq!(
+      Ok(String::from(
+        "unsigned char data[3] = {\n  0x61, 0x62, 0x63\n};"
+      )),
+      export_array(&[0x61, 0x62, 0x63], ArrayOptions {
+        per_line: 16,
+        language: ArrayLanguage::C,
+        ..Default::default()
+      })
+    );
+  }
+
+  #[test]
+  fn exports_array_rust_wraps_lines() {
+    assert_eq!(
+      Ok(String::from(
+        "const DATA: [u8; 3] = [\n  0x61, 0x62,\n  0x63\n];"
+      )),
+      export_array(&[0x61, 0x62, 0x63], ArrayOptions {
+        per_line: 2,
+        language: ArrayLanguage::Rust,
+        ..Default::default()
+      })
+    );
+  }
+
+  #[test]
+  fn exports_with_color() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[0x00, 0x61, 0xff], ExportOptions {
+      with_ascii: true,
+      with_offsets: false,
+      per_line: 3,
+      with_color: true,
+      ..Default::default()
+    }));
+    assert_eq!(
+      "\u{1b}[90m00\u{1b}[0m \u{1b}[32m61\u{1b}[0m \u{1b}[31mFF\u{1b}[0m \
+       \u{1b}[90m.\u{1b}[0m\u{1b}[32ma\u{1b}[0m\u{1b}[31m.\u{1b}[0m",
+      &actual
+    );
+  }
+
+  #[test]
+  fn colored_dump_round_trips_through_import() {
+    let mut dumped = String::new();
+    let values = [0x00, 0x61, 0xff];
+    export_to(&mut dumped, &values, ExportOptions {
+      with_ascii: true,
+      with_offsets: false,
+      per_line: 3,
+      with_color: true,
+      ..Default::default()
+    }).unwrap();
+
+    assert_eq!(Some(values.to_vec()), import(&dumped));
+  }
+
+  #[test]
+  fn exports_grouped_bytes() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[
+      0x00, 0x01, 0xfe, 0xff, 0x61, 0x62, 0x63, 0x64
+    ], ExportOptions {
+      with_ascii: false,
+      with_offsets: true,
+      per_line: 8,
+      group_size: 2,
+      ..Default::default()
+    }));
+    assert_eq!("0000 0001 FEFF 6162 6364", &actual);
+  }
+
+  #[test]
+  fn export_rejects_grouped_bytes_with_ascii() {
+    // The trailing ASCII column is the same shape as a grouped data cell
+    // (>2 hex digits, even length), so `import` can't tell them apart --
+    // e.g. bytes spelling "dead" would be re-parsed as an extra group.
+    let mut actual = String::new();
+    assert_eq!(Err(ExportError::BadOptions), export_to(&mut actual, &[
+      0x00, 0x01, 0xfe, 0xff, 0x61, 0x62
+    ], ExportOptions {
+      with_ascii: true,
+      with_offsets: true,
+      per_line: 8,
+      group_size: 2,
+      ..Default::default()
+    }));
+  }
+
+  #[test]
+  fn grouped_export_with_offsets_round_trips_through_import() {
+    let values = [0x00, 0x01, 0xfe, 0xff, 0x61, 0x62, 0x63, 0x64];
+    let mut dumped = String::new();
+    export_to(&mut dumped, &values, ExportOptions {
+      with_offsets: true,
+      per_line: 8,
+      group_size: 2,
+      ..Default::default()
+    }).unwrap();
+
+    assert_eq!(Some(values.to_vec()), import(&dumped));
+  }
+
+  #[test]
+  fn export_rejects_grouped_bytes_without_offsets() {
+    // Without an offset field, a grouped data cell is indistinguishable
+    // from the offset itself, and `import` would silently drop it.
+    let mut actual = String::new();
+    assert_eq!(Err(ExportError::BadOptions), export_to(&mut actual, &[
+      0x00, 0x01, 0xfe, 0xff
+    ], ExportOptions {
+      with_offsets: false,
+      per_line: 8,
+      group_size: 2,
+      ..Default::default()
+    }));
+  }
+
+  #[test]
+  fn export_to_rejects_squeeze_without_offsets() {
+    // Without an offset field, import's `*` expansion has no way to know
+    // how many times to repeat the previous line.
+    let mut actual = String::new();
+    assert_eq!(Err(ExportError::BadOptions), export_to(&mut actual, &[
+      1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2
+    ], ExportOptions {
+      with_offsets: false,
+      per_line: 4,
+      squeeze: true,
+      ..Default::default()
+    }));
+  }
+
+  #[test]
+  fn export_stream_rejects_squeeze_without_offsets() {
+    let mut reader = ::std::io::Cursor::new(vec![1, 1, 1, 1, 1, 1, 1, 1]);
+    let mut writer = Vec::new();
+
+    assert_eq!(
+      ::std::io::ErrorKind::InvalidInput,
+      export_stream(&mut reader, &mut writer, ExportOptions {
+        with_offsets: false,
+        per_line: 4,
+        squeeze: true,
+        ..Default::default()
+      }).unwrap_err().kind()
+    );
+  }
+
+  #[test]
+  fn export_stream_matches_export_to() {
+    let mut reader = ::std::io::Cursor::new(vec![0u8, 1, 2, 3]);
+    let mut writer = Vec::new();
+
+    export_stream(&mut reader, &mut writer, ExportOptions {
+      with_ascii: false,
+      with_offsets: false,
+      per_line: 2,
+      ..Default::default()
+    }).unwrap();
+
+    assert_eq!("00 01\n02 03", ::std::str::from_utf8(&writer).unwrap());
+  }
+
+  #[test]
+  fn export_stream_with_offsets_and_ascii_across_chunks() {
+    let mut reader = ::std::io::Cursor::new(vec![
+      0x61, 0x62, 0x63, 0x64, 0x65, 0x00
+    ]);
+    let mut writer = Vec::new();
+
+    export_stream(&mut reader, &mut writer, ExportOptions {
+      with_ascii: true,
+      with_offsets: true,
+      per_line: 4,
+      ..Default::default()
+    }).unwrap();
+
+    assert_eq!(
+      "00000000 61 62 63 64 abcd\n00000004 65 00       e.",
+      ::std::str::from_utf8(&writer).unwrap()
+    );
+  }
+
+  #[test]
+  fn export_stream_honors_skip_and_length_like_export_to() {
+    let mut reader = ::std::io::Cursor::new(vec![
+      0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07
+    ]);
+    let mut writer = Vec::new();
+
+    export_stream(&mut reader, &mut writer, ExportOptions {
+      with_offsets: true,
+      per_line: 2,
+      skip: 4,
+      length: Some(4),
+      ..Default::default()
+    }).unwrap();
+
+    assert_eq!(
+      "00000004 04 05\n00000006 06 07",
+      ::std::str::from_utf8(&writer).unwrap()
+    );
+  }
+
+  #[test]
+  fn export_stream_skip_past_the_end_of_the_reader_is_empty() {
+    let mut reader = ::std::io::Cursor::new(vec![0x00, 0x01, 0x02]);
+    let mut writer = Vec::new();
+
+    export_stream(&mut reader, &mut writer, ExportOptions {
+      per_line: 2,
+      skip: 10,
+      ..Default::default()
+    }).unwrap();
+
+    assert_eq!("", ::std::str::from_utf8(&writer).unwrap());
+  }
+
+  #[test]
+  fn export_stream_squeezes_repeated_lines() {
+    let mut reader = ::std::io::Cursor::new(vec![
+      1, 1, 1, 1,
+      1, 1, 1, 1,
+      1, 1, 1, 1,
+      2, 2, 2, 2,
+    ]);
+    let mut writer = Vec::new();
+
+    export_stream(&mut reader, &mut writer, ExportOptions {
+      with_offsets: true,
+      per_line: 4,
+      squeeze: true,
+      ..Default::default()
+    }).unwrap();
+
+    assert_eq!(
+      "00000000 01 01 01 01\n*\n0000000C 02 02 02 02",
+      ::std::str::from_utf8(&writer).unwrap()
+    );
+  }
+
+  #[test]
+  fn import_expands_squeezed_lines() {
+    assert_eq!(
+      Some(vec![1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2]),
+      import("0000 01 01 01 01\n*\n000C 02 02 02 02")
+    );
+  }
+
+  #[test]
+  fn exports_squeezes_repeated_lines() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[
+      1, 1, 1, 1,
+      1, 1, 1, 1,
+      1, 1, 1, 1,
+      2, 2, 2, 2,
+    ], ExportOptions {
+      with_ascii: false,
+      with_offsets: true,
+      per_line: 4,
+      squeeze: true,
+      ..Default::default()
+    }));
+    assert_eq!("0000 01 01 01 01\n*\n000C 02 02 02 02", &actual);
+  }
+
+  #[test]
+  fn exports_a_windowed_region_with_true_offsets() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[
+      0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07
+    ], ExportOptions {
+      with_ascii: false,
+      with_offsets: true,
+      per_line: 2,
+      skip: 4,
+      length: Some(4),
+      ..Default::default()
+    }));
+    assert_eq!("0004 04 05\n0006 06 07", &actual);
+  }
+
+  #[test]
+  fn export_window_clamps_to_the_end_of_values() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[0, 1, 2, 3], ExportOptions {
+      with_ascii: false,
+      with_offsets: false,
+      per_line: 16,
+      skip: 2,
+      length: Some(100),
+      ..Default::default()
+    }));
+    assert_eq!("02 03", &actual);
+  }
+
+  #[test]
+  fn export_window_past_the_end_of_values_is_empty() {
+    let mut actual = String::new();
+    assert_eq!(Ok(()), export_to(&mut actual, &[0, 1, 2, 3], ExportOptions {
+      with_ascii: false,
+      with_offsets: false,
+      per_line: 16,
+      skip: 10,
+      ..Default::default()
+    }));
+    assert_eq!("", &actual);
+  }
+
+  #[test]
+  fn squeezed_dump_round_trips_through_import() {
+    let values = [
+      1, 1, 1, 1,
+      1, 1, 1, 1,
+      1, 1, 1, 1,
+      2, 2, 2, 2,
+    ];
+    let mut dumped = String::new();
+    export_to(&mut dumped, &values, ExportOptions {
+      with_ascii: false,
+      with_offsets: true,
+      per_line: 4,
+      squeeze: true,
+      ..Default::default()
+    }).unwrap();
+
+    assert_eq!(Some(values.to_vec()), import(&dumped));
+  }
+
+  #[test]
+  fn squeezed_windowed_dump_round_trips_through_import() {
+    let values = [
+      9, 9, 9, 9,
+      1, 1, 1, 1,
+      1, 1, 1, 1,
+      2, 2, 2, 2,
+    ];
+    let mut dumped = String::new();
+    export_to(&mut dumped, &values, ExportOptions {
+      with_offsets: true,
+      per_line: 4,
+      skip: 4,
+      squeeze: true,
+      ..Default::default()
+    }).unwrap();
+
+    assert_eq!("0004 01 01 01 01\n*\n000C 02 02 02 02", &dumped);
+    assert_eq!(Some(values[4..].to_vec()), import(&dumped));
+  }
 }